@@ -5,10 +5,13 @@ use base64::{engine::general_purpose::STANDARD, Engine};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
-use tauri::{AppHandle, Manager};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager, State};
 
 mod wallpaper_engine;
-use wallpaper_engine::RenderOptions;
+use wallpaper_engine::{RenderOptions, WallpaperCard, WallpaperLayout};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Task {
@@ -169,12 +172,227 @@ fn get_app_data_dir() -> String {
         .to_string()
 }
 
+/// A connected display, reported to the frontend for multi-monitor setups.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MonitorInfo {
+    pub id: String,
+    pub width: u32,
+    pub height: u32,
+    pub work_x: i32,
+    pub work_y: i32,
+    pub work_width: u32,
+    pub work_height: u32,
+    pub scale_factor: f64,
+}
+
+#[tauri::command]
+async fn enumerate_monitors(app: AppHandle) -> Result<Vec<MonitorInfo>, String> {
+    // On Windows the id we hand out must be the same `IDesktopWallpaper` device
+    // path we later match against in `set_wallpaper_windows_monitor`, otherwise
+    // per-monitor `SetWallpaper` never fires. Everywhere else fall back to
+    // Tauri's monitor list.
+    enumerate_for_ids(&app)
+}
+
+/// Enumerate monitors through `IDesktopWallpaper`, keying each by its device
+/// path so the id round-trips to `SetWallpaper`.
+#[cfg(target_os = "windows")]
+fn enumerate_monitors_windows() -> Result<Vec<MonitorInfo>, String> {
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoTaskMemFree, CLSCTX_ALL, COINIT_APARTMENTTHREADED,
+    };
+    use windows::Win32::UI::Shell::{DesktopWallpaper, IDesktopWallpaper};
+
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+        let desktop: IDesktopWallpaper = CoCreateInstance(&DesktopWallpaper, None, CLSCTX_ALL)
+            .map_err(|e| format!("Failed to create IDesktopWallpaper: {}", e))?;
+
+        let count = desktop
+            .GetMonitorDevicePathCount()
+            .map_err(|e| format!("GetMonitorDevicePathCount failed: {}", e))?;
+
+        let mut monitors = Vec::with_capacity(count as usize);
+        for i in 0..count {
+            let device = desktop
+                .GetMonitorDevicePathAt(i)
+                .map_err(|e| format!("GetMonitorDevicePathAt failed: {}", e))?;
+            let id = device.to_string().unwrap_or_default();
+            let rect = desktop.GetMonitorRECT(&device);
+            // The shell allocates the device path; free it once we've copied out
+            // the id and no longer need it for `GetMonitorRECT`.
+            CoTaskMemFree(Some(device.0 as *const _));
+            let rect = rect.map_err(|e| format!("GetMonitorRECT failed: {}", e))?;
+            let width = (rect.right - rect.left).max(0) as u32;
+            let height = (rect.bottom - rect.top).max(0) as u32;
+            monitors.push(MonitorInfo {
+                id,
+                width,
+                height,
+                work_x: rect.left,
+                work_y: rect.top,
+                work_width: width,
+                work_height: height,
+                scale_factor: 1.0,
+            });
+        }
+        Ok(monitors)
+    }
+}
+
 #[tauri::command]
 async fn render_wallpaper_native(
+    app: AppHandle,
     background_path: String,
     options: RenderOptions,
+    monitor_id: Option<String>,
+    layout: Option<WallpaperLayout>,
+) -> Result<String, String> {
+    let layout = layout.unwrap_or_default();
+
+    // Resolve the target monitor's resolution (if one was requested) so the
+    // background is fitted to that display before cards are composited.
+    let target = match &monitor_id {
+        Some(id) => monitor_size_for_id(&app, id)?,
+        None => None,
+    };
+
+    let png_data = wallpaper_engine::render_wallpaper_sized(&background_path, &options, target, layout)?;
+
+    // Use a per-monitor filename so mixed-resolution displays keep distinct
+    // wallpapers on disk.
+    let file_name = match &monitor_id {
+        Some(id) => format!("cjw_wallpaper_{}.png", sanitize_id(id)),
+        None => "cjw_wallpaper.png".to_string(),
+    };
+    let wallpaper_path = get_wallpaper_dir().join(file_name);
+    fs::write(&wallpaper_path, &png_data)
+        .map_err(|e| format!("Failed to save wallpaper: {}", e))?;
+
+    set_wallpaper(wallpaper_path.to_str().unwrap(), monitor_id.as_deref())?;
+    Ok(wallpaper_path.to_string_lossy().to_string())
+}
+
+/// Resolve a monitor id to its pixel resolution, using the same id scheme as
+/// [`enumerate_monitors`] so the lookup actually matches.
+fn monitor_size_for_id(app: &AppHandle, id: &str) -> Result<Option<(u32, u32)>, String> {
+    let monitor = enumerate_for_ids(app)?
+        .into_iter()
+        .find(|m| m.id == id);
+    Ok(monitor.map(|m| (m.width, m.height)))
+}
+
+/// Shared monitor enumeration used by both the Tauri command and internal
+/// resolution lookups.
+fn enumerate_for_ids(app: &AppHandle) -> Result<Vec<MonitorInfo>, String> {
+    #[cfg(target_os = "windows")]
+    {
+        let _ = app;
+        return enumerate_monitors_windows();
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let window = app
+            .get_webview_window("main")
+            .ok_or("Main window not available")?;
+        let monitors = window
+            .available_monitors()
+            .map_err(|e| format!("Failed to enumerate monitors: {}", e))?;
+        Ok(monitors
+            .into_iter()
+            .enumerate()
+            .map(|(i, m)| {
+                let size = m.size();
+                let pos = m.position();
+                MonitorInfo {
+                    id: m.name().cloned().unwrap_or_else(|| format!("monitor-{}", i)),
+                    width: size.width,
+                    height: size.height,
+                    work_x: pos.x,
+                    work_y: pos.y,
+                    work_width: size.width,
+                    work_height: size.height,
+                    scale_factor: m.scale_factor(),
+                }
+            })
+            .collect())
+    }
+}
+
+/// Make a monitor id safe to embed in a filename.
+fn sanitize_id(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Set the wallpaper, targeting a specific monitor on Windows when an id is
+/// given. Everywhere else (and when no monitor is specified) the whole desktop
+/// is set through the `wallpaper` crate.
+fn set_wallpaper(path: &str, monitor_id: Option<&str>) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        if let Some(id) = monitor_id {
+            return set_wallpaper_windows_monitor(path, id);
+        }
+    }
+    let _ = monitor_id;
+    wallpaper::set_from_path(path).map_err(|e| format!("Failed to set wallpaper: {}", e))
+}
+
+/// Per-monitor wallpaper via `IDesktopWallpaper::SetWallpaper`.
+#[cfg(target_os = "windows")]
+fn set_wallpaper_windows_monitor(path: &str, monitor_id: &str) -> Result<(), String> {
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoTaskMemFree, CLSCTX_ALL, COINIT_APARTMENTTHREADED,
+    };
+    use windows::Win32::UI::Shell::{DesktopWallpaper, IDesktopWallpaper};
+
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+        let desktop: IDesktopWallpaper = CoCreateInstance(&DesktopWallpaper, None, CLSCTX_ALL)
+            .map_err(|e| format!("Failed to create IDesktopWallpaper: {}", e))?;
+
+        // Match the requested id against the device paths Windows reports.
+        let count = desktop
+            .GetMonitorDevicePathCount()
+            .map_err(|e| format!("GetMonitorDevicePathCount failed: {}", e))?;
+        let wide_path = HSTRING::from(path);
+        for i in 0..count {
+            let device = desktop
+                .GetMonitorDevicePathAt(i)
+                .map_err(|e| format!("GetMonitorDevicePathAt failed: {}", e))?;
+            let device_str = device.to_string().unwrap_or_default();
+            let matched = device_str == monitor_id;
+            let result = if matched {
+                Some(
+                    desktop
+                        .SetWallpaper(PCWSTR(device.0), PCWSTR(wide_path.as_ptr()))
+                        .map_err(|e| format!("SetWallpaper failed: {}", e)),
+                )
+            } else {
+                None
+            };
+            // The shell owns the device path string; free it after use.
+            CoTaskMemFree(Some(device.0 as *const _));
+            if let Some(result) = result {
+                return result;
+            }
+        }
+    }
+    // Fall back to a full-desktop set if the id did not match a device path.
+    wallpaper::set_from_path(path).map_err(|e| format!("Failed to set wallpaper: {}", e))
+}
+
+#[tauri::command]
+async fn render_wallpaper_from_scene(
+    background_path: String,
+    scene_path: String,
+    cards: Vec<WallpaperCard>,
 ) -> Result<String, String> {
-    let png_data = wallpaper_engine::render_wallpaper(&background_path, &options)?;
+    let png_data =
+        wallpaper_engine::render_wallpaper_from_scene(&background_path, &scene_path, &cards)?;
     let wallpaper_path = get_wallpaper_dir().join("cjw_wallpaper.png");
     fs::write(&wallpaper_path, &png_data)
         .map_err(|e| format!("Failed to save wallpaper: {}", e))?;
@@ -183,8 +401,192 @@ async fn render_wallpaper_native(
     Ok(wallpaper_path.to_string_lossy().to_string())
 }
 
+// ============ Wallpaper Rotation ============
+
+/// A persisted wallpaper rotation schedule.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RotationSchedule {
+    /// Seconds between wallpaper refreshes.
+    pub interval_secs: u64,
+    /// Files and/or directories to cycle through.
+    pub sources: Vec<String>,
+    /// Card data + render settings used for the overlay.
+    pub options: RenderOptions,
+    /// When true keep the current background and only refresh the card overlay
+    /// (e.g. when tasks change) instead of advancing to the next image.
+    #[serde(default)]
+    pub rerender_only_when_tasks_change: bool,
+}
+
+/// Managed state for the rotation task: the running task handle (if any) plus
+/// the live card overlay data, which the frontend keeps up to date as tasks
+/// change so each re-render shows current tasks rather than a frozen snapshot.
+#[derive(Default)]
+pub struct RotationState {
+    handle: Arc<Mutex<Option<JoinHandle<()>>>>,
+    cards: Arc<Mutex<Vec<WallpaperCard>>>,
+}
+
+fn rotation_schedule_path() -> PathBuf {
+    get_wallpaper_dir().join("rotation_schedule.json")
+}
+
+fn save_schedule(schedule: &RotationSchedule) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(schedule)
+        .map_err(|e| format!("Failed to serialize schedule: {}", e))?;
+    fs::write(rotation_schedule_path(), json)
+        .map_err(|e| format!("Failed to save schedule: {}", e))
+}
+
+fn load_schedule() -> Option<RotationSchedule> {
+    let data = fs::read_to_string(rotation_schedule_path()).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+fn clear_schedule() {
+    fs::remove_file(rotation_schedule_path()).ok();
+}
+
+/// Expand a list of files/directories into an ordered list of valid images.
+fn collect_rotation_images(sources: &[String]) -> Vec<PathBuf> {
+    let mut images = Vec::new();
+    for source in sources {
+        let path = PathBuf::from(source);
+        if path.is_dir() {
+            if let Ok(entries) = fs::read_dir(&path) {
+                let mut dir_images: Vec<PathBuf> = entries
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| wallpaper_engine::is_valid_image(p))
+                    .collect();
+                dir_images.sort();
+                images.extend(dir_images);
+            }
+        } else if wallpaper_engine::is_valid_image(&path) {
+            images.push(path);
+        }
+    }
+    images
+}
+
+/// Render the overlay over `background_path` and set it as the wallpaper.
+fn render_and_set(background_path: &str, options: &RenderOptions) -> Result<(), String> {
+    let png_data = wallpaper_engine::render_wallpaper(background_path, options)?;
+    let wallpaper_path = get_wallpaper_dir().join("cjw_wallpaper.png");
+    fs::write(&wallpaper_path, &png_data)
+        .map_err(|e| format!("Failed to save wallpaper: {}", e))?;
+    wallpaper::set_from_path(wallpaper_path.to_str().unwrap())
+        .map_err(|e| format!("Failed to set wallpaper: {}", e))
+}
+
+/// A cheap fingerprint of the card overlay, used to skip byte-identical
+/// re-renders in "only when tasks change" mode.
+fn cards_fingerprint(cards: &[WallpaperCard]) -> String {
+    serde_json::to_string(cards).unwrap_or_default()
+}
+
+/// Spawn the background rotation task, replacing any previously running one.
+fn spawn_rotation(state: &RotationState, schedule: RotationSchedule) {
+    // Cancel any task already running before starting a new one.
+    if let Some(existing) = state.handle.lock().unwrap().take() {
+        existing.abort();
+    }
+
+    // Seed the shared live-card store from the schedule so the first render
+    // matches the caller's request until the frontend pushes an update.
+    *state.cards.lock().unwrap() = schedule.options.cards.clone();
+    let live_cards = Arc::clone(&state.cards);
+
+    let handle = tauri::async_runtime::spawn(async move {
+        let images = collect_rotation_images(&schedule.sources);
+        if images.is_empty() {
+            return;
+        }
+
+        let interval = Duration::from_secs(schedule.interval_secs.max(1));
+        let mut ticker = tokio::time::interval(interval);
+        let mut index = 0usize;
+        let mut last_fingerprint: Option<String> = None;
+
+        loop {
+            ticker.tick().await;
+
+            // Always render with the latest card data so the overlay reflects
+            // current tasks rather than the snapshot taken at start time.
+            let mut options = schedule.options.clone();
+            options.cards = live_cards.lock().unwrap().clone();
+
+            // In "tasks change" mode keep the first background and skip the
+            // render entirely when the cards are unchanged, so we don't burn
+            // CPU re-encoding a byte-identical PNG every tick. Otherwise advance
+            // through the image list on each tick.
+            let image = if schedule.rerender_only_when_tasks_change {
+                let fingerprint = cards_fingerprint(&options.cards);
+                if last_fingerprint.as_deref() == Some(fingerprint.as_str()) {
+                    continue;
+                }
+                last_fingerprint = Some(fingerprint);
+                &images[0]
+            } else {
+                let img = &images[index % images.len()];
+                index = index.wrapping_add(1);
+                img
+            };
+
+            if let Some(path) = image.to_str() {
+                if let Err(e) = render_and_set(path, &options) {
+                    eprintln!("wallpaper rotation failed: {}", e);
+                }
+            }
+        }
+    });
+
+    *state.handle.lock().unwrap() = Some(handle);
+}
+
+#[tauri::command]
+async fn start_wallpaper_rotation(
+    state: State<'_, RotationState>,
+    interval_secs: u64,
+    sources: Vec<String>,
+    options: RenderOptions,
+    rerender_only_when_tasks_change: Option<bool>,
+) -> Result<(), String> {
+    let schedule = RotationSchedule {
+        interval_secs,
+        sources,
+        options,
+        rerender_only_when_tasks_change: rerender_only_when_tasks_change.unwrap_or(false),
+    };
+    save_schedule(&schedule)?;
+    spawn_rotation(state.inner(), schedule);
+    Ok(())
+}
+
+/// Push the current task/memo cards into the running rotation so the next tick
+/// re-renders with up-to-date data. In "only when tasks change" mode this is
+/// what triggers a refresh.
+#[tauri::command]
+async fn update_rotation_cards(
+    state: State<'_, RotationState>,
+    cards: Vec<WallpaperCard>,
+) -> Result<(), String> {
+    *state.cards.lock().unwrap() = cards;
+    Ok(())
+}
+
+#[tauri::command]
+async fn stop_wallpaper_rotation(state: State<'_, RotationState>) -> Result<(), String> {
+    if let Some(handle) = state.handle.lock().unwrap().take() {
+        handle.abort();
+    }
+    clear_schedule();
+    Ok(())
+}
+
 fn main() {
     tauri::Builder::default()
+        .manage(RotationState::default())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_sql::Builder::new().build())
         .plugin(tauri_plugin_dialog::init())
@@ -212,9 +614,19 @@ fn main() {
             show_splash,
             get_app_data_dir,
             render_wallpaper_native,
+            enumerate_monitors,
+            render_wallpaper_from_scene,
+            start_wallpaper_rotation,
+            stop_wallpaper_rotation,
+            update_rotation_cards,
         ])
         .setup(|app| {
             let _spotlight = app.get_webview_window("spotlight");
+            // Resume a persisted rotation schedule across restarts.
+            if let Some(schedule) = load_schedule() {
+                let state = app.state::<RotationState>();
+                spawn_rotation(state.inner(), schedule);
+            }
             Ok(())
         })
         .on_window_event(|window, event| {
@@ -1,11 +1,12 @@
 //! Wallpaper Engine Module
 //! High-performance native rendering using tiny-skia
 
+use cosmic_text::{Attrs, Buffer, Family, FontSystem, Metrics, Shaping, SwashCache, Weight};
 use image::GenericImageView;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use tiny_skia::{
-    Color, FillRule, Paint, PathBuilder, Pixmap, Rect, Transform,
+    Color, FillRule, Paint, PathBuilder, Pixmap, PremultipliedColorU8, Rect, Transform,
 };
 
 /// Card data to render on wallpaper
@@ -32,7 +33,52 @@ pub struct RenderOptions {
     pub card_width: u32,
     pub card_opacity: f32,
     pub blur_background: bool,
+    #[serde(default)]
+    pub blur_radius: u32,
     pub is_dark_mode: bool,
+    /// Gaussian radius of the card drop shadow, in pixels.
+    #[serde(default = "default_shadow_blur")]
+    pub shadow_blur: f32,
+    /// How far the drop shadow is offset down-right from the card.
+    #[serde(default = "default_shadow_offset")]
+    pub shadow_offset: f32,
+    /// Opacity of the drop shadow (0.0 disables it).
+    #[serde(default = "default_shadow_opacity")]
+    pub shadow_opacity: f32,
+}
+
+fn default_shadow_blur() -> f32 {
+    8.0
+}
+
+fn default_shadow_offset() -> f32 {
+    4.0
+}
+
+fn default_shadow_opacity() -> f32 {
+    0.35
+}
+
+/// How the background image is fitted to a monitor's resolution.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WallpaperLayout {
+    /// Keep native size, centered; crop or pad to the monitor.
+    Center,
+    /// Stretch to the monitor resolution, ignoring aspect ratio.
+    Stretch,
+    /// Scale preserving aspect ratio to cover the monitor, cropping overflow.
+    Fill,
+    /// Scale preserving aspect ratio to fit inside, letterboxing the remainder.
+    Fit,
+    /// Repeat the source across the monitor.
+    Tile,
+}
+
+impl Default for WallpaperLayout {
+    fn default() -> Self {
+        WallpaperLayout::Fill
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,7 +98,11 @@ impl Default for RenderOptions {
             card_width: 280,
             card_opacity: 0.85,
             blur_background: false,
+            blur_radius: 12,
             is_dark_mode: true,
+            shadow_blur: default_shadow_blur(),
+            shadow_offset: default_shadow_offset(),
+            shadow_opacity: default_shadow_opacity(),
         }
     }
 }
@@ -71,19 +121,63 @@ pub fn is_valid_image(path: &Path) -> bool {
 pub fn render_wallpaper(
     background_path: &str,
     options: &RenderOptions,
+) -> Result<Vec<u8>, String> {
+    render_wallpaper_sized(background_path, options, None, WallpaperLayout::default())
+}
+
+/// Build the `FontSystem` used for a render.
+///
+/// Normally this scans the system font directories via [`FontSystem::new`].
+/// When `CJW_WALLPAPER_FONT_DIR` is set the font database is instead populated
+/// *only* from that directory, which makes glyph rasterization deterministic
+/// across machines — used by the golden-image reftests so their references do
+/// not depend on whatever fonts happen to be installed.
+fn build_font_system() -> FontSystem {
+    if let Some(dir) = std::env::var_os("CJW_WALLPAPER_FONT_DIR") {
+        let mut db = cosmic_text::fontdb::Database::new();
+        db.load_fonts_dir(dir);
+        db.set_sans_serif_family("DejaVu Sans");
+        db.set_serif_family("DejaVu Serif");
+        db.set_monospace_family("DejaVu Sans Mono");
+        FontSystem::new_with_locale_and_db("en-US".to_string(), db)
+    } else {
+        FontSystem::new()
+    }
+}
+
+/// Render cards onto a background that is first fitted to `target` (a monitor
+/// resolution) using `layout`. When `target` is `None` the background's native
+/// size is used unchanged, preserving the original single-display behavior.
+pub fn render_wallpaper_sized(
+    background_path: &str,
+    options: &RenderOptions,
+    target: Option<(u32, u32)>,
+    layout: WallpaperLayout,
 ) -> Result<Vec<u8>, String> {
     // Load background image
     let background = image::open(background_path)
         .map_err(|e| format!("Failed to load background: {}", e))?;
-    
-    let (width, height) = background.dimensions();
-    
-    // Create pixmap from background
+
+    let (width, height) = match target {
+        Some((w, h)) if w > 0 && h > 0 => (w, h),
+        _ => background.dimensions(),
+    };
+
+    // Create pixmap at the output resolution
     let mut pixmap = Pixmap::new(width, height)
         .ok_or("Failed to create pixmap")?;
-    
-    // Copy background to pixmap
-    let bg_rgba = background.to_rgba8();
+
+    // Fit the background to the output resolution per the requested layout.
+    let mut bg_rgba = fit_background(&background, width, height, layout);
+
+    // Optional frosted-glass backdrop: blur the background before cards are
+    // composited. Only the region under the card stack is blurred so large
+    // 4K wallpapers stay fast.
+    if options.blur_background && options.blur_radius > 0 {
+        let region = card_stack_bounds(options, width, height);
+        gaussian_blur_rgba(&mut bg_rgba, width, height, options.blur_radius as f32, region);
+    }
+
     for (x, y, pixel) in bg_rgba.enumerate_pixels() {
         pixmap.pixels_mut()[(y * width + x) as usize] = 
             tiny_skia::PremultipliedColorU8::from_rgba(
@@ -99,20 +193,324 @@ pub fn render_wallpaper(
     
     // Render cards
     if !options.cards.is_empty() {
-        render_cards(&mut pixmap, options, width, height);
+        // One font system + glyph cache per render keeps font loading off the
+        // per-card hot path.
+        let mut font_system = build_font_system();
+        let mut swash_cache = SwashCache::new();
+        render_cards(
+            &mut pixmap,
+            &mut font_system,
+            &mut swash_cache,
+            options,
+            width,
+            height,
+        );
     }
-    
+
     // Encode to PNG
     pixmap.encode_png().map_err(|e| format!("Failed to encode PNG: {}", e))
 }
 
+/// Fit `background` into a `width`x`height` RGBA canvas according to `layout`.
+fn fit_background(
+    background: &image::DynamicImage,
+    width: u32,
+    height: u32,
+    layout: WallpaperLayout,
+) -> image::RgbaImage {
+    use image::imageops::FilterType;
+
+    let (sw, sh) = background.dimensions();
+    if sw == 0 || sh == 0 {
+        return image::RgbaImage::new(width, height);
+    }
+
+    // Fast path: source already matches the canvas.
+    if sw == width && sh == height && matches!(layout, WallpaperLayout::Stretch | WallpaperLayout::Fill | WallpaperLayout::Fit) {
+        return background.to_rgba8();
+    }
+
+    match layout {
+        WallpaperLayout::Stretch => {
+            image::imageops::resize(&background.to_rgba8(), width, height, FilterType::Lanczos3)
+        }
+        WallpaperLayout::Center => {
+            let mut canvas = image::RgbaImage::new(width, height);
+            let src = background.to_rgba8();
+            let ox = (width as i64 - sw as i64) / 2;
+            let oy = (height as i64 - sh as i64) / 2;
+            image::imageops::overlay(&mut canvas, &src, ox, oy);
+            canvas
+        }
+        WallpaperLayout::Fill | WallpaperLayout::Fit => {
+            let scale_cover = (width as f32 / sw as f32).max(height as f32 / sh as f32);
+            let scale_contain = (width as f32 / sw as f32).min(height as f32 / sh as f32);
+            let scale = if matches!(layout, WallpaperLayout::Fill) {
+                scale_cover
+            } else {
+                scale_contain
+            };
+            let nw = (sw as f32 * scale).round().max(1.0) as u32;
+            let nh = (sh as f32 * scale).round().max(1.0) as u32;
+            let scaled = image::imageops::resize(&background.to_rgba8(), nw, nh, FilterType::Lanczos3);
+
+            let mut canvas = image::RgbaImage::new(width, height);
+            // Fill: crop overflow (negative offset). Fit: letterbox (positive).
+            let ox = (width as i64 - nw as i64) / 2;
+            let oy = (height as i64 - nh as i64) / 2;
+            image::imageops::overlay(&mut canvas, &scaled, ox, oy);
+            canvas
+        }
+        WallpaperLayout::Tile => {
+            let mut canvas = image::RgbaImage::new(width, height);
+            let src = background.to_rgba8();
+            let mut y = 0i64;
+            while y < height as i64 {
+                let mut x = 0i64;
+                while x < width as i64 {
+                    image::imageops::overlay(&mut canvas, &src, x, y);
+                    x += sw as i64;
+                }
+                y += sh as i64;
+            }
+            canvas
+        }
+    }
+}
+
+/// Generous bounding box (x, y, w, h) covering where the card stack lands, so
+/// the blur only touches that corner of the wallpaper. Returns `None` when
+/// there are no cards (caller then blurs the whole image).
+fn card_stack_bounds(options: &RenderOptions, width: u32, height: u32) -> Option<(u32, u32, u32, u32)> {
+    if options.cards.is_empty() {
+        return None;
+    }
+
+    let card_width = options.card_width as f32;
+    let margin = 32.0_f32;
+    // Upper bound per card (tall card + inter-card margin) and up to 4 cards.
+    let max_card = 180.0_f32;
+    let stack_h = (options.cards.len().min(4) as f32) * (max_card + 12.0);
+
+    let box_w = card_width + margin * 2.0;
+    let box_h = stack_h + margin;
+
+    let (bx, by) = match options.position {
+        CardPosition::BottomRight => (width as f32 - box_w, height as f32 - box_h),
+        CardPosition::BottomLeft => (0.0, height as f32 - box_h),
+        CardPosition::TopRight => (width as f32 - box_w, 0.0),
+        CardPosition::TopLeft => (0.0, 0.0),
+    };
+
+    let x = bx.max(0.0) as u32;
+    let y = by.max(0.0) as u32;
+    let w = (box_w as u32).min(width - x.min(width));
+    let h = (box_h as u32).min(height - y.min(height));
+    Some((x, y, w.max(1), h.max(1)))
+}
+
+/// Approximate a Gaussian blur with three successive box blurs over the RGBA
+/// buffer. When `region` is given only that sub-rectangle is blurred.
+fn gaussian_blur_rgba(
+    buf: &mut image::RgbaImage,
+    width: u32,
+    height: u32,
+    sigma: f32,
+    region: Option<(u32, u32, u32, u32)>,
+) {
+    // Box radius matching the target sigma for three passes.
+    let radius = (sigma * (12.0_f32 / 3.0 + 1.0).sqrt() / 2.0).round() as i32;
+    if radius < 1 {
+        return;
+    }
+
+    let (rx, ry, rw, rh) = region.unwrap_or((0, 0, width, height));
+    let (rw, rh) = (rw as usize, rh as usize);
+
+    // Extract the region into a tight RGBA scratch buffer.
+    let mut src = vec![0u8; rw * rh * 4];
+    for y in 0..rh {
+        for x in 0..rw {
+            let p = buf.get_pixel((rx + x as u32).min(width - 1), (ry + y as u32).min(height - 1));
+            let o = (y * rw + x) * 4;
+            src[o..o + 4].copy_from_slice(&p.0);
+        }
+    }
+    let mut tmp = vec![0u8; rw * rh * 4];
+
+    // Three box-blur passes (horizontal then vertical each) ≈ Gaussian.
+    for _ in 0..3 {
+        box_blur_horizontal(&src, &mut tmp, rw, rh, radius);
+        box_blur_vertical(&tmp, &mut src, rw, rh, radius);
+    }
+
+    // Write the blurred region back.
+    for y in 0..rh {
+        for x in 0..rw {
+            let o = (y * rw + x) * 4;
+            let mut px = image::Rgba([0u8; 4]);
+            px.0.copy_from_slice(&src[o..o + 4]);
+            buf.put_pixel(rx + x as u32, ry + y as u32, px);
+        }
+    }
+}
+
+/// Sliding-window horizontal box blur over a tight RGBA buffer.
+fn box_blur_horizontal(src: &[u8], dst: &mut [u8], w: usize, h: usize, radius: i32) {
+    let window = (2 * radius + 1) as f32;
+    for y in 0..h {
+        let row = y * w * 4;
+        for c in 0..4 {
+            let mut sum = 0i32;
+            for k in -radius..=radius {
+                let xi = (k.clamp(0, w as i32 - 1)) as usize;
+                sum += src[row + xi * 4 + c] as i32;
+            }
+            for x in 0..w {
+                dst[row + x * 4 + c] = (sum as f32 / window).round() as u8;
+                let x_out = (x as i32 - radius).clamp(0, w as i32 - 1) as usize;
+                let x_in = (x as i32 + radius + 1).clamp(0, w as i32 - 1) as usize;
+                sum += src[row + x_in * 4 + c] as i32 - src[row + x_out * 4 + c] as i32;
+            }
+        }
+    }
+}
+
+/// Sliding-window vertical box blur over a tight RGBA buffer.
+fn box_blur_vertical(src: &[u8], dst: &mut [u8], w: usize, h: usize, radius: i32) {
+    let window = (2 * radius + 1) as f32;
+    for x in 0..w {
+        for c in 0..4 {
+            let mut sum = 0i32;
+            for k in -radius..=radius {
+                let yi = (k.clamp(0, h as i32 - 1)) as usize;
+                sum += src[(yi * w + x) * 4 + c] as i32;
+            }
+            for y in 0..h {
+                dst[(y * w + x) * 4 + c] = (sum as f32 / window).round() as u8;
+                let y_out = (y as i32 - radius).clamp(0, h as i32 - 1) as usize;
+                let y_in = (y as i32 + radius + 1).clamp(0, h as i32 - 1) as usize;
+                sum += src[(y_in * w + x) * 4 + c] as i32 - src[(y_out * w + x) * 4 + c] as i32;
+            }
+        }
+    }
+}
+
+/// Render a feathered drop shadow beneath a card. The shadow path is drawn
+/// into its own layer, blurred with the same box blur used for the background,
+/// then composited under the card body.
+#[allow(clippy::too_many_arguments)]
+fn draw_card_shadow(
+    pixmap: &mut Pixmap,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    radius: f32,
+    blur: f32,
+    offset: f32,
+    opacity: f32,
+) {
+    // Pad the layer so the feathered penumbra is not clipped.
+    let pad = (blur * 3.0).ceil().max(1.0) + offset.abs();
+    let tw = (width + pad * 2.0).ceil() as u32;
+    let th = (height + pad * 2.0).ceil() as u32;
+    let Some(mut layer) = Pixmap::new(tw, th) else {
+        return;
+    };
+
+    // Draw the card silhouette in low-alpha black, offset down-right.
+    let alpha = (opacity.clamp(0.0, 1.0) * 255.0) as u8;
+    draw_rounded_rect(
+        &mut layer,
+        pad + offset,
+        pad + offset,
+        width,
+        height,
+        radius,
+        Color::from_rgba8(0, 0, 0, alpha),
+        Transform::identity(),
+    );
+
+    // Blur just the shadow layer into a feathered penumbra.
+    let mut shadow = pixmap_to_rgba(&layer);
+    gaussian_blur_rgba(&mut shadow, tw, th, blur, None);
+
+    // Composite the shadow under where the card will be drawn.
+    composite_rgba_over(pixmap, &shadow, (x - pad).round() as i32, (y - pad).round() as i32);
+}
+
+/// Copy a pixmap's (premultiplied) pixels into a straight-alpha RGBA image.
+/// The shadow is pure black, so the colour channels are already correct.
+fn pixmap_to_rgba(pixmap: &Pixmap) -> image::RgbaImage {
+    let mut img = image::RgbaImage::new(pixmap.width(), pixmap.height());
+    for (dst, src) in img.pixels_mut().zip(pixmap.pixels()) {
+        *dst = image::Rgba([src.red(), src.green(), src.blue(), src.alpha()]);
+    }
+    img
+}
+
+/// Alpha-blend a straight-alpha RGBA layer over the pixmap at (ox, oy).
+fn composite_rgba_over(pixmap: &mut Pixmap, layer: &image::RgbaImage, ox: i32, oy: i32) {
+    let pw = pixmap.width() as i32;
+    let ph = pixmap.height() as i32;
+    let (lw, lh) = layer.dimensions();
+    let pixels = pixmap.pixels_mut();
+
+    for ly in 0..lh as i32 {
+        let py = oy + ly;
+        if py < 0 || py >= ph {
+            continue;
+        }
+        for lx in 0..lw as i32 {
+            let px = ox + lx;
+            if px < 0 || px >= pw {
+                continue;
+            }
+            let s = layer.get_pixel(lx as u32, ly as u32);
+            let sa = s.0[3] as f32 / 255.0;
+            if sa <= 0.0 {
+                continue;
+            }
+            let inv = 1.0 - sa;
+            let idx = (py * pw + px) as usize;
+            let dst = pixels[idx];
+            let out_r = s.0[0] as f32 * sa + dst.red() as f32 * inv;
+            let out_g = s.0[1] as f32 * sa + dst.green() as f32 * inv;
+            let out_b = s.0[2] as f32 * sa + dst.blue() as f32 * inv;
+            let out_a = sa * 255.0 + dst.alpha() as f32 * inv;
+            pixels[idx] = PremultipliedColorU8::from_rgba(
+                out_r.round().clamp(0.0, 255.0) as u8,
+                out_g.round().clamp(0.0, 255.0) as u8,
+                out_b.round().clamp(0.0, 255.0) as u8,
+                out_a.round().clamp(0.0, 255.0) as u8,
+            )
+            .unwrap_or(dst);
+        }
+    }
+}
+
 /// Render card widgets on the pixmap
-fn render_cards(pixmap: &mut Pixmap, options: &RenderOptions, width: u32, height: u32) {
+fn render_cards(
+    pixmap: &mut Pixmap,
+    font_system: &mut FontSystem,
+    swash_cache: &mut SwashCache,
+    options: &RenderOptions,
+    width: u32,
+    height: u32,
+) {
     let card_width = options.card_width as f32;
     let card_padding = 16.0_f32;
     let card_margin = 12.0_f32;
     let corner_radius = 16.0_f32;
-    
+
+    // Text color: white on dark mode, near-black on light mode.
+    let text_color = if options.is_dark_mode {
+        Color::from_rgba8(255, 255, 255, 255)
+    } else {
+        Color::from_rgba8(24, 24, 27, 255)
+    };
+
     // Calculate starting position based on card position
     let (start_x, start_y) = match options.position {
         CardPosition::BottomRight => (
@@ -123,13 +521,16 @@ fn render_cards(pixmap: &mut Pixmap, options: &RenderOptions, width: u32, height
         CardPosition::TopRight => (width as f32 - card_width - 32.0, 32.0),
         CardPosition::TopLeft => (32.0, 32.0),
     };
-    
+
     let mut current_y = start_y;
-    
+
     // Render each card (from bottom to top for bottom positions)
     for card in options.cards.iter().take(4) {
-        let card_height = calculate_card_height(card, card_width, card_padding);
-        
+        // Shape the card text first so the card height follows the real
+        // wrapped layout instead of a character-count guess.
+        let buffer = shape_card_text(font_system, card, card_width, card_padding, 16.0, 20.0);
+        let card_height = shaped_card_height(&buffer, card_padding);
+
         // Adjust Y for bottom positions (stack upward)
         let card_y = match options.position {
             CardPosition::BottomRight | CardPosition::BottomLeft => {
@@ -142,7 +543,22 @@ fn render_cards(pixmap: &mut Pixmap, options: &RenderOptions, width: u32, height
                 y
             }
         };
-        
+
+        // Soft drop shadow under the card for elevation and contrast.
+        if options.shadow_opacity > 0.0 {
+            draw_card_shadow(
+                pixmap,
+                start_x,
+                card_y,
+                card_width,
+                card_height,
+                corner_radius,
+                options.shadow_blur,
+                options.shadow_offset,
+                options.shadow_opacity,
+            );
+        }
+
         // Draw card background (frosted glass effect)
         draw_rounded_rect(
             pixmap,
@@ -156,8 +572,9 @@ fn render_cards(pixmap: &mut Pixmap, options: &RenderOptions, width: u32, height
             } else {
                 Color::from_rgba8(0, 0, 0, (options.card_opacity * 30.0) as u8)
             },
+            Transform::identity(),
         );
-        
+
         // Draw card border
         draw_rounded_rect_stroke(
             pixmap,
@@ -172,6 +589,7 @@ fn render_cards(pixmap: &mut Pixmap, options: &RenderOptions, width: u32, height
                 Color::from_rgba8(0, 0, 0, 20)
             },
             1.0,
+            Transform::identity(),
         );
         
         // Draw pin indicator for pinned items
@@ -186,18 +604,361 @@ fn render_cards(pixmap: &mut Pixmap, options: &RenderOptions, width: u32, height
                 card_y + 12.0,
                 6.0,
                 pin_color,
+                Transform::identity(),
             );
         }
+
+        // Draw the shaped title + content over the card body.
+        draw_card_text(
+            pixmap,
+            font_system,
+            swash_cache,
+            &buffer,
+            start_x + card_padding,
+            card_y + card_padding,
+            text_color,
+        );
     }
 }
 
-fn calculate_card_height(card: &WallpaperCard, _width: f32, padding: f32) -> f32 {
-    // Estimate height based on content length
-    let title_height = if card.title.is_empty() { 0.0 } else { 24.0 };
-    let content_lines = (card.content.len() as f32 / 30.0).ceil().max(1.0);
-    let content_height = content_lines * 20.0;
-    
-    title_height + content_height + padding * 2.0
+/// Shape a card's title (bold) and content into a word-wrapped buffer.
+fn shape_card_text(
+    font_system: &mut FontSystem,
+    card: &WallpaperCard,
+    card_width: f32,
+    padding: f32,
+    font_size: f32,
+    line_height: f32,
+) -> Buffer {
+    let metrics = Metrics {
+        font_size,
+        line_height,
+    };
+    let mut buffer = Buffer::new(font_system, metrics);
+
+    // Constrain the width so cosmic-text wraps to the card; leave the height
+    // unbounded so nothing is clipped while we measure.
+    buffer.set_size(font_system, card_width - padding * 2.0, f32::INFINITY);
+
+    let title = card.title.trim();
+    let content = card.content.trim();
+    let body = if title.is_empty() {
+        content.to_string()
+    } else if content.is_empty() {
+        format!("{}\n", title)
+    } else {
+        format!("{}\n{}", title, content)
+    };
+
+    // Bold for the title line, regular weight for the content below it.
+    let bold = Attrs::new().family(Family::SansSerif).weight(Weight::BOLD);
+    let regular = Attrs::new().family(Family::SansSerif);
+    let title_len = if title.is_empty() { 0 } else { title.len() + 1 };
+    let spans = [(&body[..title_len], bold), (&body[title_len..], regular)];
+    buffer.set_rich_text(font_system, spans, regular, Shaping::Advanced);
+
+    buffer.shape_until_scroll(font_system, false);
+    buffer
+}
+
+/// Real wrapped height of a shaped card buffer plus vertical padding.
+fn shaped_card_height(buffer: &Buffer, padding: f32) -> f32 {
+    let line_height = buffer.metrics().line_height;
+    let lines = buffer.layout_runs().count().max(1) as f32;
+    lines * line_height + padding * 2.0
+}
+
+/// Rasterize the shaped glyphs and alpha-blend them onto the pixmap.
+fn draw_card_text(
+    pixmap: &mut Pixmap,
+    font_system: &mut FontSystem,
+    swash_cache: &mut SwashCache,
+    buffer: &Buffer,
+    origin_x: f32,
+    origin_y: f32,
+    color: Color,
+) {
+    for run in buffer.layout_runs() {
+        for glyph in run.glyphs.iter() {
+            let physical = glyph.physical((0.0, 0.0), 1.0);
+            let Some(image) = swash_cache.get_image(font_system, physical.cache_key).as_ref() else {
+                continue;
+            };
+            if image.placement.width == 0 || image.placement.height == 0 {
+                continue;
+            }
+
+            let glyph_x = origin_x + glyph.x + image.placement.left as f32;
+            let glyph_y = origin_y + run.line_y + glyph.y - image.placement.top as f32;
+            blit_coverage(pixmap, image, glyph_x.round() as i32, glyph_y.round() as i32, color);
+        }
+    }
+}
+
+/// Blit an 8-bit coverage bitmap at (x, y), compositing `color` over the
+/// existing premultiplied pixels.
+fn blit_coverage(
+    pixmap: &mut Pixmap,
+    image: &cosmic_text::SwashImage,
+    x: i32,
+    y: i32,
+    color: Color,
+) {
+    let pw = pixmap.width() as i32;
+    let ph = pixmap.height() as i32;
+    let gw = image.placement.width as i32;
+    let gh = image.placement.height as i32;
+
+    let cr = color.red();
+    let cg = color.green();
+    let cb = color.blue();
+    let pixels = pixmap.pixels_mut();
+
+    for gy in 0..gh {
+        let py = y + gy;
+        if py < 0 || py >= ph {
+            continue;
+        }
+        for gx in 0..gw {
+            let px = x + gx;
+            if px < 0 || px >= pw {
+                continue;
+            }
+            let coverage = image.data[(gy * gw + gx) as usize] as f32 / 255.0;
+            if coverage <= 0.0 {
+                continue;
+            }
+
+            // Source is fully opaque text weighted by glyph coverage; blend
+            // over the premultiplied destination pixel.
+            let idx = (py * pw + px) as usize;
+            let dst = pixels[idx];
+            let inv = 1.0 - coverage;
+            let out_r = (cr * coverage * 255.0) + dst.red() as f32 * inv;
+            let out_g = (cg * coverage * 255.0) + dst.green() as f32 * inv;
+            let out_b = (cb * coverage * 255.0) + dst.blue() as f32 * inv;
+            let out_a = coverage * 255.0 + dst.alpha() as f32 * inv;
+            pixels[idx] = PremultipliedColorU8::from_rgba(
+                out_r.round().clamp(0.0, 255.0) as u8,
+                out_g.round().clamp(0.0, 255.0) as u8,
+                out_b.round().clamp(0.0, 255.0) as u8,
+                out_a.round().clamp(0.0, 255.0) as u8,
+            )
+            .unwrap_or(dst);
+        }
+    }
+}
+
+/// Which point of a card slot its `x`/`y` coordinate refers to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Anchor {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Center,
+}
+
+impl Default for Anchor {
+    fn default() -> Self {
+        Anchor::TopLeft
+    }
+}
+
+fn default_slot_font_size() -> f32 {
+    16.0
+}
+
+fn default_slot_opacity() -> f32 {
+    0.85
+}
+
+/// One placed card in a declarative scene.
+///
+/// Note: slots are always rendered axis-aligned. Arbitrary rotation is not
+/// supported — the glyph rasterizer blits coverage axis-aligned, so a rotated
+/// body would carry upright text spilling out of its frame. A `rotation` key in
+/// a scene file is accepted for forward compatibility but ignored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardSlot {
+    /// Optional id used to merge live card data by name instead of by index.
+    #[serde(default)]
+    pub id: Option<String>,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    #[serde(default)]
+    pub anchor: Anchor,
+    #[serde(default = "default_slot_font_size")]
+    pub font_size: f32,
+    #[serde(default = "default_slot_opacity")]
+    pub opacity: f32,
+}
+
+/// A declarative wallpaper layout loaded from a RON or YAML file. Slots
+/// describe exactly where cards go, replacing the four fixed corners.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WallpaperScene {
+    pub slots: Vec<CardSlot>,
+    #[serde(default)]
+    pub is_dark_mode: bool,
+}
+
+/// Render `cards` into a background using a declarative `scene` read from a RON
+/// or YAML file. Live cards are merged into slots by id (when a slot names one)
+/// otherwise by position in the list.
+pub fn render_wallpaper_from_scene(
+    background_path: &str,
+    scene_path: &str,
+    cards: &[WallpaperCard],
+) -> Result<Vec<u8>, String> {
+    let scene = load_scene(scene_path)?;
+
+    let background = image::open(background_path)
+        .map_err(|e| format!("Failed to load background: {}", e))?;
+    let (width, height) = background.dimensions();
+
+    let mut pixmap = Pixmap::new(width, height).ok_or("Failed to create pixmap")?;
+    let bg_rgba = background.to_rgba8();
+    for (x, y, pixel) in bg_rgba.enumerate_pixels() {
+        // Premultiply explicitly: `PremultipliedColorU8::from_rgba` returns
+        // `None` for a straight-alpha pixel whose channel exceeds its alpha, so
+        // feeding raw RGBA would panic on a background with semi-transparent
+        // pixels.
+        pixmap.pixels_mut()[(y * width + x) as usize] =
+            tiny_skia::ColorU8::from_rgba(pixel[0], pixel[1], pixel[2], pixel[3]).premultiply();
+    }
+
+    let mut font_system = build_font_system();
+    let mut swash_cache = SwashCache::new();
+    render_scene_cards(&mut pixmap, &mut font_system, &mut swash_cache, &scene, cards);
+
+    pixmap.encode_png().map_err(|e| format!("Failed to encode PNG: {}", e))
+}
+
+/// Load a `WallpaperScene` from a `.ron`, `.yaml`, or `.yml` file.
+fn load_scene(scene_path: &str) -> Result<WallpaperScene, String> {
+    let text = std::fs::read_to_string(scene_path)
+        .map_err(|e| format!("Failed to read scene file: {}", e))?;
+    let ext = Path::new(scene_path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match ext.as_str() {
+        "ron" => ron::from_str(&text).map_err(|e| format!("Failed to parse RON scene: {}", e)),
+        "yaml" | "yml" => {
+            serde_yaml::from_str(&text).map_err(|e| format!("Failed to parse YAML scene: {}", e))
+        }
+        other => Err(format!("Unsupported scene format: .{}", other)),
+    }
+}
+
+/// Merge live card data into a scene's slot for `index`: prefer a slot whose
+/// `id` matches the card's title, else fall back to positional matching.
+fn card_for_slot<'a>(
+    slot: &CardSlot,
+    index: usize,
+    cards: &'a [WallpaperCard],
+) -> Option<&'a WallpaperCard> {
+    if let Some(id) = &slot.id {
+        if let Some(card) = cards.iter().find(|c| &c.title == id) {
+            return Some(card);
+        }
+    }
+    cards.get(index)
+}
+
+/// Generalized card renderer that honors per-slot geometry instead of a single
+/// `CardPosition`.
+fn render_scene_cards(
+    pixmap: &mut Pixmap,
+    font_system: &mut FontSystem,
+    swash_cache: &mut SwashCache,
+    scene: &WallpaperScene,
+    cards: &[WallpaperCard],
+) {
+    let card_padding = 16.0_f32;
+    let corner_radius = 16.0_f32;
+
+    let text_color = if scene.is_dark_mode {
+        Color::from_rgba8(255, 255, 255, 255)
+    } else {
+        Color::from_rgba8(24, 24, 27, 255)
+    };
+
+    for (index, slot) in scene.slots.iter().enumerate() {
+        let Some(card) = card_for_slot(slot, index, cards) else {
+            continue;
+        };
+
+        let line_height = slot.font_size * 1.25;
+        let buffer = shape_card_text(
+            font_system,
+            card,
+            slot.width,
+            card_padding,
+            slot.font_size,
+            line_height,
+        );
+        let card_height = shaped_card_height(&buffer, card_padding);
+
+        // Resolve the slot's anchor into a top-left origin.
+        let (x0, y0) = match slot.anchor {
+            Anchor::TopLeft => (slot.x, slot.y),
+            Anchor::TopRight => (slot.x - slot.width, slot.y),
+            Anchor::BottomLeft => (slot.x, slot.y - card_height),
+            Anchor::BottomRight => (slot.x - slot.width, slot.y - card_height),
+            Anchor::Center => (slot.x - slot.width / 2.0, slot.y - card_height / 2.0),
+        };
+
+        // Slots render axis-aligned; see `CardSlot` for why rotation is
+        // unsupported.
+        let transform = Transform::identity();
+
+        let fill = if scene.is_dark_mode {
+            Color::from_rgba8(255, 255, 255, (slot.opacity * 40.0) as u8)
+        } else {
+            Color::from_rgba8(0, 0, 0, (slot.opacity * 30.0) as u8)
+        };
+        let border = if scene.is_dark_mode {
+            Color::from_rgba8(255, 255, 255, 50)
+        } else {
+            Color::from_rgba8(0, 0, 0, 20)
+        };
+
+        draw_rounded_rect(pixmap, x0, y0, slot.width, card_height, corner_radius, fill, transform);
+        draw_rounded_rect_stroke(
+            pixmap,
+            x0,
+            y0,
+            slot.width,
+            card_height,
+            corner_radius,
+            border,
+            1.0,
+            transform,
+        );
+
+        if card.is_pinned {
+            let pin_color = match card.card_type {
+                CardType::Memo => Color::from_rgba8(251, 191, 36, 255),
+                CardType::Task => Color::from_rgba8(96, 165, 250, 255),
+            };
+            draw_circle(pixmap, x0 + slot.width - 12.0, y0 + 12.0, 6.0, pin_color, transform);
+        }
+
+        draw_card_text(
+            pixmap,
+            font_system,
+            swash_cache,
+            &buffer,
+            x0 + card_padding,
+            y0 + card_padding,
+            text_color,
+        );
+    }
 }
 
 fn draw_rounded_rect(
@@ -208,9 +969,10 @@ fn draw_rounded_rect(
     height: f32,
     radius: f32,
     color: Color,
+    transform: Transform,
 ) {
     let mut pb = PathBuilder::new();
-    
+
     // Top-left corner
     pb.move_to(x + radius, y);
     // Top edge
@@ -235,7 +997,7 @@ fn draw_rounded_rect(
         let mut paint = Paint::default();
         paint.set_color(color);
         paint.anti_alias = true;
-        pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+        pixmap.fill_path(&path, &paint, FillRule::Winding, transform, None);
     }
 }
 
@@ -248,9 +1010,10 @@ fn draw_rounded_rect_stroke(
     radius: f32,
     color: Color,
     stroke_width: f32,
+    transform: Transform,
 ) {
     let mut pb = PathBuilder::new();
-    
+
     pb.move_to(x + radius, y);
     pb.line_to(x + width - radius, y);
     pb.quad_to(x + width, y, x + width, y + radius);
@@ -271,19 +1034,19 @@ fn draw_rounded_rect_stroke(
             width: stroke_width,
             ..Default::default()
         };
-        pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+        pixmap.stroke_path(&path, &paint, &stroke, transform, None);
     }
 }
 
-fn draw_circle(pixmap: &mut Pixmap, cx: f32, cy: f32, radius: f32, color: Color) {
+fn draw_circle(pixmap: &mut Pixmap, cx: f32, cy: f32, radius: f32, color: Color, transform: Transform) {
     let mut pb = PathBuilder::new();
     pb.push_circle(cx, cy, radius);
-    
+
     if let Some(path) = pb.finish() {
         let mut paint = Paint::default();
         paint.set_color(color);
         paint.anti_alias = true;
-        pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+        pixmap.fill_path(&path, &paint, FillRule::Winding, transform, None);
     }
 }
 
@@ -0,0 +1,178 @@
+//! Golden-image reference tests for the wallpaper renderer.
+//!
+//! Each case renders a fixed `RenderOptions` over the committed background
+//! fixture and compares the output against a stored golden PNG with a small
+//! per-pixel tolerance. Run with `UPDATE_GOLDEN=1 cargo test` to (re)write the
+//! golden references after an intentional rendering change; the diffs then show
+//! up as reviewable image changes in a PR.
+
+// The wallpaper engine is a module of the binary crate, so pull it in directly
+// rather than through a library target.
+#[path = "../src/wallpaper_engine.rs"]
+mod wallpaper_engine;
+
+use std::path::{Path, PathBuf};
+
+use image::RgbaImage;
+use wallpaper_engine::{CardPosition, CardType, RenderOptions, WallpaperCard};
+
+/// Maximum allowed per-channel absolute difference for a pixel to count as equal.
+const CHANNEL_TOLERANCE: i32 = 2;
+/// Maximum number of differing pixels tolerated before a case fails.
+///
+/// Glyph rasterization is never bit-identical across `cosmic-text`/`swash`
+/// versions and sub-pixel rounding, so a pixel-exact budget is not portable
+/// even with a pinned font. This tolerates a small fraction of the image
+/// changing (a few hundred antialiased glyph-edge pixels) while still catching
+/// real layout/color regressions, which move thousands of pixels.
+const MAX_DIFF_PIXELS: u64 = 1500;
+
+/// Point the renderer at the committed fixture fonts so glyph output does not
+/// depend on whichever fonts `FontSystem::new()` would find on the host.
+fn use_fixture_fonts() {
+    std::env::set_var("CJW_WALLPAPER_FONT_DIR", fixtures_dir().join("fonts"));
+}
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden")
+}
+
+fn sample_cards() -> Vec<WallpaperCard> {
+    vec![
+        WallpaperCard {
+            title: "Groceries".into(),
+            content: "Milk, eggs, coffee beans and a loaf of sourdough bread".into(),
+            card_type: CardType::Memo,
+            is_pinned: true,
+        },
+        WallpaperCard {
+            title: "Ship release".into(),
+            content: "Tag v1.2 and update the changelog".into(),
+            card_type: CardType::Task,
+            is_pinned: false,
+        },
+    ]
+}
+
+fn base_options(position: CardPosition, is_dark_mode: bool, card_count: usize) -> RenderOptions {
+    RenderOptions {
+        cards: sample_cards().into_iter().take(card_count).collect(),
+        position,
+        is_dark_mode,
+        ..Default::default()
+    }
+}
+
+/// Render a case and assert it matches its golden image (or rewrite it when
+/// `UPDATE_GOLDEN` is set).
+fn check_case(name: &str, options: &RenderOptions) {
+    use_fixture_fonts();
+    let background = fixtures_dir().join("background.png");
+    let png = wallpaper_engine::render_wallpaper(background.to_str().unwrap(), options)
+        .unwrap_or_else(|e| panic!("render failed for {name}: {e}"));
+    let actual = image::load_from_memory(&png)
+        .expect("decode rendered PNG")
+        .to_rgba8();
+
+    let golden_path = golden_dir().join(format!("{name}.png"));
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(golden_dir()).unwrap();
+        actual.save(&golden_path).expect("write golden");
+        return;
+    }
+
+    let golden = match image::open(&golden_path) {
+        Ok(img) => img.to_rgba8(),
+        Err(_) => {
+            actual.save(golden_dir().join(format!("{name}.actual.png"))).ok();
+            panic!(
+                "missing golden {}; run with UPDATE_GOLDEN=1 to create it",
+                golden_path.display()
+            );
+        }
+    };
+
+    if let Some(diff) = compare(&golden, &actual) {
+        actual.save(golden_dir().join(format!("{name}.actual.png"))).ok();
+        diff.image
+            .save(golden_dir().join(format!("{name}.diff.png")))
+            .ok();
+        panic!(
+            "{name} differs from golden: {} pixels over tolerance (max {})",
+            diff.diff_pixels, MAX_DIFF_PIXELS
+        );
+    }
+}
+
+struct Diff {
+    diff_pixels: u64,
+    image: RgbaImage,
+}
+
+/// Compare two images; returns `None` when they match within tolerance, or a
+/// diff image highlighting changed pixels in magenta otherwise.
+fn compare(golden: &RgbaImage, actual: &RgbaImage) -> Option<Diff> {
+    if golden.dimensions() != actual.dimensions() {
+        let mut image = actual.clone();
+        for px in image.pixels_mut() {
+            *px = image::Rgba([255, 0, 255, 255]);
+        }
+        return Some(Diff {
+            diff_pixels: (actual.width() as u64) * (actual.height() as u64),
+            image,
+        });
+    }
+
+    let mut diff_pixels = 0u64;
+    let mut image = actual.clone();
+    for (g, (x, y, a)) in golden.pixels().zip(actual.enumerate_pixels()) {
+        let over_tolerance = (0..4).any(|c| (g.0[c] as i32 - a.0[c] as i32).abs() > CHANNEL_TOLERANCE);
+        if over_tolerance {
+            diff_pixels += 1;
+            image.put_pixel(x, y, image::Rgba([255, 0, 255, 255]));
+        }
+    }
+
+    if diff_pixels > MAX_DIFF_PIXELS {
+        Some(Diff { diff_pixels, image })
+    } else {
+        None
+    }
+}
+
+#[test]
+fn bottom_right_dark_two_cards() {
+    check_case(
+        "bottom_right_dark_two",
+        &base_options(CardPosition::BottomRight, true, 2),
+    );
+}
+
+#[test]
+fn bottom_left_light_two_cards() {
+    check_case(
+        "bottom_left_light_two",
+        &base_options(CardPosition::BottomLeft, false, 2),
+    );
+}
+
+#[test]
+fn top_right_dark_one_card() {
+    check_case(
+        "top_right_dark_one",
+        &base_options(CardPosition::TopRight, true, 1),
+    );
+}
+
+#[test]
+fn top_left_light_one_card() {
+    check_case(
+        "top_left_light_one",
+        &base_options(CardPosition::TopLeft, false, 1),
+    );
+}